@@ -1,16 +1,223 @@
-use std::collections::HashMap;
-use crate::dom::node::{AttrMap, element, Node, text};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use crate::dom::node::{AttrMap, comment, doctype, element, Node, NodeType, text};
+
+/// Returns the table of named HTML character references recognized by `decode_entities`.
+///
+/// This only covers a practical subset of the HTML5 named character reference table, not the
+/// full list; unrecognized names fall through and are left as literal text by the caller.
+fn named_entities() -> &'static HashMap<&'static str, &'static str> {
+    static ENTITIES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    ENTITIES.get_or_init(|| {
+        HashMap::from([
+            ("amp", "&"),
+            ("lt", "<"),
+            ("gt", ">"),
+            ("quot", "\""),
+            ("apos", "'"),
+            ("nbsp", "\u{a0}"),
+            ("copy", "\u{a9}"),
+            ("reg", "\u{ae}"),
+            ("mdash", "\u{2014}"),
+            ("ndash", "\u{2013}"),
+            ("hellip", "\u{2026}"),
+            ("trade", "\u{2122}"),
+        ])
+    })
+}
+
+/// Decodes a single character reference starting at `s[0]` (which must be `&`).
+///
+/// Returns the decoded replacement text and the number of bytes it consumed from `s`, or `None`
+/// if `s` doesn't start with a well-formed, known reference.
+fn decode_one_entity(s: &str) -> Option<(String, usize)> {
+    let after_amp = &s[1..];
+    if let Some(rest) = after_amp.strip_prefix('#') {
+        let is_hex = matches!(rest.chars().next(), Some('x') | Some('X'));
+        let digits = if is_hex { &rest[1..] } else { rest };
+        let end = digits.find(';')?;
+        let code = &digits[..end];
+        if code.is_empty() {
+            return None;
+        }
+        let code_point = if is_hex {
+            u32::from_str_radix(code, 16).ok()?
+        } else {
+            code.parse::<u32>().ok()?
+        };
+        let ch = char::from_u32(code_point)?;
+        let consumed = "&#".len() + usize::from(is_hex) + code.len() + ";".len();
+        Some((ch.to_string(), consumed))
+    } else {
+        let end = after_amp.find(';')?;
+        let name = &after_amp[..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let replacement = *named_entities().get(name)?;
+        let consumed = "&".len() + name.len() + ";".len();
+        Some((replacement.to_string(), consumed))
+    }
+}
+
+/// Replaces HTML character references (`&amp;`, `&#169;`, `&#x2764;`, ...) in `input` with the
+/// characters they represent.
+///
+/// An unterminated or unrecognized reference is left in the output unchanged rather than
+/// rejected, matching how browsers render broken markup.
+fn decode_entities(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(idx) = rest.find('&') {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        match decode_one_entity(rest) {
+            Some((decoded, consumed)) => {
+                out.push_str(&decoded);
+                rest = &rest[consumed..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The kind of problem encountered while parsing an HTML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input ended while the parser still expected more content.
+    UnexpectedEof,
+    /// A closing tag was found, but its name didn't match the currently open element.
+    MismatchedClosingTag { expected: String, found: String },
+    /// An attribute value was missing its opening/closing quote.
+    UnterminatedAttribute,
+    /// A specific character was expected but a different one was found.
+    UnexpectedToken { expected: char, found: char },
+    /// An attribute was expected but the next character can't start one.
+    InvalidAttributeName { found: char },
+}
+
+/// Returns the set of HTML void element tag names, i.e. elements that never have children or a
+/// closing tag (`<br>`, `<img src="x">`, ...).
+fn void_elements() -> &'static HashSet<&'static str> {
+    static VOID_ELEMENTS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    VOID_ELEMENTS.get_or_init(|| {
+        HashSet::from([
+            "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param",
+            "source", "track", "wbr",
+        ])
+    })
+}
+
+/// Returns the set of raw-text tags that are true CDATA, i.e. their content is never entity-
+/// decoded (`script`, `style`). The remaining raw-text tags (`textarea`, `title`, and any custom
+/// tags registered via `add_code_tag`) are RCDATA: their content is still parsed as a single text
+/// run, but character references in it are decoded like ordinary text.
+fn cdata_tags() -> &'static HashSet<&'static str> {
+    static CDATA_TAGS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    CDATA_TAGS.get_or_init(|| HashSet::from(["script", "style"]))
+}
+
+/// An error produced while parsing an HTML document, with its source position.
+///
+/// `line` and `column` are 1-based and 0-based respectively, counted from the
+/// start of the input; `byte_offset` is the raw byte index into the source string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub byte_offset: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ErrorKind::MismatchedClosingTag { expected, found } => {
+                write!(f, "expected closing tag </{}>, found </{}>", expected, found)
+            }
+            ErrorKind::UnterminatedAttribute => write!(f, "unterminated attribute value"),
+            ErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ErrorKind::InvalidAttributeName { found } => {
+                write!(f, "expected an attribute name, found '{}'", found)
+            }
+        }
+        .and_then(|_| write!(f, " at {}:{}", self.line, self.column))
+    }
+}
+
+impl std::error::Error for ParseError {}
 
 /// A parser for handling HTML document strings.
 ///
-/// This struct keeps track of the current position (`pos`) within the input string (`input`).
-/// It provides methods to parse and extract nodes and elements from the HTML document string.
+/// This struct keeps track of the current position (`pos`) within the input string (`input`),
+/// as well as the `line`/`col` that position corresponds to, so errors can report where in the
+/// source they occurred. It provides methods to parse and extract nodes and elements from the
+/// HTML document string.
 pub struct Parser {
     pos: usize,
+    line: usize,
+    col: usize,
     input: String,
+    /// When `true`, recoverable errors (such as a mismatched closing tag) are repaired in place
+    /// instead of aborting the parse, mirroring how real HTML tokenizers recover from broken markup.
+    recover: bool,
+    /// When `true` (the default), character references in text and attribute values are decoded.
+    /// Callers that need to round-trip the source verbatim can disable this.
+    decode_entities: bool,
+    /// Tag names (lowercase) whose content is consumed verbatim as a single text child instead of
+    /// being parsed as markup, e.g. `script`/`style`. See `add_code_tag`.
+    code_tags: HashSet<String>,
 }
 
 impl Parser {
+    /// Creates a parser with default options (strict mode, character references decoded, and the
+    /// standard raw-text tags `script`/`style`/`textarea`/`title`). Configure it further with
+    /// `with_recovery`/`with_entity_decoding`/`add_code_tag` before calling `parse_document`.
+    pub fn new() -> Self {
+        Parser {
+            pos: 0,
+            line: 1,
+            col: 0,
+            input: String::new(),
+            recover: false,
+            decode_entities: true,
+            code_tags: ["script", "style", "textarea", "title"].iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Registers a tag name whose content should be treated as raw text (consumed verbatim up to
+    /// its matching closing tag) rather than parsed as markup, in addition to the defaults
+    /// (`script`, `style`, `textarea`, `title`). Matching is case-insensitive.
+    pub fn add_code_tag(&mut self, name: impl Into<String>) {
+        self.code_tags.insert(name.into().to_lowercase());
+    }
+
+    /// Enables or disables lenient/recovery mode. See `parse_lenient`.
+    pub fn with_recovery(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Enables or disables decoding of character references (`&amp;`, `&#169;`, ...) in text and
+    /// attribute values. Disable this to keep raw source text for round-tripping.
+    pub fn with_entity_decoding(mut self, decode_entities: bool) -> Self {
+        self.decode_entities = decode_entities;
+        self
+    }
+
+    /// Builds a `ParseError` of the given kind at the parser's current position.
+    fn error(&self, kind: ErrorKind) -> ParseError {
+        ParseError { kind, line: self.line, column: self.col, byte_offset: self.pos }
+    }
+
     /// Returns the next character in the input string at the current position.
     ///
     /// # Panics
@@ -48,7 +255,8 @@ impl Parser {
 
     /// Consumes and returns the character at the current position in the input string.
     ///
-    /// Advances the position to the next character in the string.
+    /// Advances the position to the next character in the string, and keeps `line`/`col` in
+    /// sync with it (a consumed `\n` advances `line` and resets `col` to 0).
     ///
     /// # Returns
     ///
@@ -57,12 +265,36 @@ impl Parser {
     pub fn consume_char(&mut self) -> char {
         if let Some((idx, ch)) = self.input[self.pos..].char_indices().next() {
             self.pos += idx + ch.len_utf8();
+            if ch == '\n' {
+                self.line += 1;
+                self.col = 0;
+            } else {
+                self.col += 1;
+            }
             ch
         } else {
             ' '
         }
     }
 
+    /// Consumes the next character and checks that it matches `expected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorKind::UnexpectedEof` if the input is exhausted, or
+    /// `ErrorKind::UnexpectedToken` if a different character was found.
+    fn expect_char(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.eof() {
+            return Err(self.error(ErrorKind::UnexpectedEof));
+        }
+        let err = self.error(ErrorKind::UnexpectedToken { expected, found: self.next_char() });
+        let found = self.consume_char();
+        if found != expected {
+            return Err(err);
+        }
+        Ok(())
+    }
+
     /// Consumes characters from the input string while the provided predicate function returns true.
     ///
     /// The method iterates through the input string, consuming characters and appending them
@@ -106,19 +338,62 @@ impl Parser {
         self.consume_while(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9'))
     }
 
-    /// Parses a node in the HTML document based on the next character encountered.
+    /// Parses a node in the HTML document based on what comes next.
     ///
-    /// If the next character is '<', it parses an element node using `parse_element()`.
-    /// Otherwise, it parses text content using `parse_text()`.
+    /// `<!--` starts a comment, `<!` starts a doctype (or other bang-) declaration, `<` starts an
+    /// element via `parse_element()`, and anything else is parsed as text via `parse_text()`.
     ///
     /// # Returns
     ///
-    /// A `Node` representing either an element or text, based on the next character in the input.
-    pub fn parse_node(&mut self) -> Node {
+    /// A `Node` representing the comment, doctype, element, or text that was parsed, or a
+    /// `ParseError` if an element is malformed and recovery isn't enabled.
+    pub fn parse_node(&mut self) -> Result<Node, ParseError> {
+        if self.starts_with("<!--") {
+            return Ok(self.parse_comment());
+        }
+        if self.starts_with("<!") {
+            return Ok(self.parse_doctype());
+        }
         match self.next_char() {
             '<' => self.parse_element(),
-            _ => self.parse_text()
+            _ => Ok(self.parse_text()),
+        }
+    }
+
+    /// Parses an HTML comment `<!-- ... -->`.
+    ///
+    /// An unterminated comment consumes to EOF and still yields a comment node. A literal `--`
+    /// that isn't immediately followed by `>` does not end the comment, so nested `--` inside
+    /// comment text doesn't prematurely terminate it.
+    fn parse_comment(&mut self) -> Node {
+        for _ in 0.."<!--".len() {
+            self.consume_char();
+        }
+        let start = self.pos;
+        while !self.eof() && !self.starts_with("-->") {
+            self.consume_char();
+        }
+        let content = self.input[start..self.pos].to_string();
+        if !self.eof() {
+            for _ in 0.."-->".len() {
+                self.consume_char();
+            }
+        }
+        comment(content)
+    }
+
+    /// Parses a bang declaration such as `<!DOCTYPE html>`, consuming through the closing `>` and
+    /// storing the raw text in between as `NodeType::Doctype`. Any `<!...>` that isn't a comment
+    /// is treated this way, so `DOCTYPE` is recognized by position rather than by keyword, which
+    /// naturally handles it appearing in any case (`<!doctype html>`, `<!DOCTYPE html>`, ...).
+    fn parse_doctype(&mut self) -> Node {
+        self.consume_char(); // '<'
+        self.consume_char(); // '!'
+        let content = self.consume_while(|c| c != '>');
+        if !self.eof() {
+            self.consume_char(); // '>'
         }
+        doctype(content)
     }
 
     /// Parses multiple nodes in the HTML document until encountering an end tag or reaching the end of the input.
@@ -128,16 +403,16 @@ impl Parser {
     /// # Returns
     ///
     /// A vector containing the parsed nodes. If an end tag is found or the input ends, the method returns all the parsed nodes up to that point.
-    pub fn parse_nodes(&mut self) -> Vec<Node> {
+    pub fn parse_nodes(&mut self) -> Result<Vec<Node>, ParseError> {
         let mut nodes = Vec::new();
         loop {
             self.consume_whitespace();
             if self.eof() || self.starts_with("</") {
                 break;
             }
-            nodes.push(self.parse_node());
+            nodes.push(self.parse_node()?);
         }
-        nodes
+        Ok(nodes)
     }
 
     /// Parses an HTML element node and its children, including attributes and closing tag.
@@ -145,29 +420,115 @@ impl Parser {
     /// This method assumes the current position in the input string points to the start of an HTML element ('<').
     /// It proceeds to parse the element's tag name, attributes, content (children nodes), and closing tag.
     ///
-    /// # Panics
+    /// Elements explicitly self-closed (`<input disabled/>`) or whose tag name is a void element
+    /// (`<br>`, `<img src="x">`, ...) are returned immediately with no children and no closing tag.
     ///
-    /// This method will panic if the expected characters ('<', '>', '</') are not found or do not match the expected structure of an HTML element.
+    /// If the closing tag doesn't match the opening tag, or the input ends before any closing tag
+    /// is found at all, the behavior depends on `recover`: in strict mode these are a
+    /// `ErrorKind::MismatchedClosingTag`/`ErrorKind::UnexpectedEof` error; in recovery mode the
+    /// element is treated as implicitly closed (a mismatched tag is left for an ancestor to
+    /// consume), the way a real HTML tokenizer recovers from broken markup.
     ///
     /// # Returns
     ///
     /// A `Node` representing the parsed HTML element, containing its tag name, attributes, and children nodes.
-    fn parse_element(&mut self) -> Node {
-        assert_eq!(self.consume_char(), '<');
+    fn parse_element(&mut self) -> Result<Node, ParseError> {
+        self.expect_char('<')?;
         let tag_name = self.parse_tag_name();
-        let attrs = self.parse_attributes();
-        assert_eq!(self.consume_char(), '>');
+        let attrs = self.parse_attributes()?;
+
+        if !self.eof() && self.next_char() == '/' {
+            self.consume_char();
+            self.expect_char('>')?;
+            return Ok(element(tag_name, attrs, Vec::new()));
+        }
+        self.expect_char('>')?;
+
+        if void_elements().contains(tag_name.as_str()) {
+            return Ok(element(tag_name, attrs, Vec::new()));
+        }
+
+        if self.code_tags.contains(&tag_name.to_lowercase()) {
+            return self.parse_raw_text_element(tag_name, attrs);
+        }
 
         // Content
-        let children = self.parse_nodes();
+        let children = self.parse_nodes()?;
 
         // Closing Tag
-        assert_eq!(self.consume_char(), '<');
-        assert_eq!(self.consume_char(), '/');
-        assert_eq!(self.parse_tag_name(), tag_name);
-        assert_eq!(self.consume_char(), '>');
+        if self.eof() {
+            if self.recover {
+                return Ok(element(tag_name, attrs, children));
+            }
+            return Err(self.error(ErrorKind::UnexpectedEof));
+        }
+        let checkpoint = (self.pos, self.line, self.col);
+        self.expect_char('<')?;
+        self.expect_char('/')?;
+        let closing_name = self.parse_tag_name();
+        if closing_name != tag_name {
+            if self.recover {
+                (self.pos, self.line, self.col) = checkpoint;
+                return Ok(element(tag_name, attrs, children));
+            }
+            return Err(self.error(ErrorKind::MismatchedClosingTag { expected: tag_name, found: closing_name }));
+        }
+        self.expect_char('>')?;
+
+        Ok(element(tag_name, attrs, children))
+    }
+
+    /// Parses the content of a raw-text element (`script`, `style`, ...) registered via
+    /// `add_code_tag`/the defaults: everything up to the matching case-insensitive closing tag is
+    /// stored as a single text child rather than parsed as markup.
+    ///
+    /// CDATA tags (`script`, `style`) keep their content verbatim. RCDATA tags (`textarea`,
+    /// `title`, and any custom tag) still have character references decoded, matching how real
+    /// HTML treats them.
+    fn parse_raw_text_element(&mut self, tag_name: String, attrs: AttrMap) -> Result<Node, ParseError> {
+        let raw = self.scan_raw_text(&tag_name);
+        let content = if self.decode_entities && !cdata_tags().contains(tag_name.to_lowercase().as_str()) {
+            decode_entities(&raw)
+        } else {
+            raw
+        };
+        let children = if content.is_empty() { Vec::new() } else { vec![text(content)] };
+
+        if self.eof() {
+            if self.recover {
+                return Ok(element(tag_name, attrs, children));
+            }
+            return Err(self.error(ErrorKind::UnexpectedEof));
+        }
+        self.expect_char('<')?;
+        self.expect_char('/')?;
+        self.parse_tag_name();
+        self.consume_whitespace();
+        self.expect_char('>')?;
 
-        element(tag_name, attrs, children)
+        Ok(element(tag_name, attrs, children))
+    }
+
+    /// Consumes characters verbatim until the matching case-insensitive `</tag_name` closing
+    /// sequence is found (not consuming the closing tag itself), for use inside raw-text elements
+    /// where embedded `<`/`>` must not be mistaken for markup.
+    ///
+    /// Runs to EOF if no matching closing tag is ever found.
+    fn scan_raw_text(&mut self, tag_name: &str) -> String {
+        let start = self.pos;
+        while !self.eof() {
+            if self.starts_with("</") {
+                let after = &self.input[self.pos + 2..];
+                let is_match = after.len() >= tag_name.len()
+                    && after[..tag_name.len()].eq_ignore_ascii_case(tag_name)
+                    && matches!(after[tag_name.len()..].chars().next(), None | Some('>' | ' ' | '\t' | '\n' | '\r'));
+                if is_match {
+                    break;
+                }
+            }
+            self.consume_char();
+        }
+        self.input[start..self.pos].to_string()
     }
 
     /// Parses text content until encountering an HTML tag ('<').
@@ -179,26 +540,35 @@ impl Parser {
     ///
     /// A `Node` representing the parsed text content until the start of an HTML tag.
     fn parse_text(&mut self) -> Node {
-        text(self.consume_while(|c| c != '<'))
+        let raw = self.consume_while(|c| c != '<');
+        if self.decode_entities {
+            text(decode_entities(&raw))
+        } else {
+            text(raw)
+        }
     }
 
     /// Parses a single attribute name-value pair within an HTML element tag.
     ///
-    /// This method parses an attribute name using `parse_tag_name()`,
-    /// followed by the equality sign '=', and then parses the attribute value using `parse_attr_value()`.
-    ///
-    /// # Panics
-    ///
-    /// This method will panic if the expected characters ('=') are not found or if the attribute name or value parsing fails.
+    /// This method parses an attribute name using `parse_tag_name()`. If the name isn't followed
+    /// by `=`, it's a valueless/boolean attribute (`<input disabled>`) and is stored with an empty
+    /// value; otherwise the value is parsed with `parse_attr_value()`.
     ///
     /// # Returns
     ///
-    /// A tuple containing the parsed attribute name and its corresponding value as strings.
-    fn parse_attr(&mut self) -> (String, String) {
+    /// A tuple containing the parsed attribute name and its corresponding value as strings, or a
+    /// `ParseError` if the next character can't start an attribute name or the value is malformed.
+    fn parse_attr(&mut self) -> Result<(String, String), ParseError> {
         let name = self.parse_tag_name();
-        assert_eq!(self.consume_char(), '=');
-        let value = self.parse_attr_value();
-        (name, value)
+        if name.is_empty() {
+            return Err(self.error(ErrorKind::InvalidAttributeName { found: self.next_char() }));
+        }
+        if self.eof() || self.next_char() != '=' {
+            return Ok((name, String::new()));
+        }
+        self.consume_char();
+        let value = self.parse_attr_value()?;
+        Ok((name, value))
     }
 
     /// Parses the value of an HTML attribute within an element tag.
@@ -206,40 +576,50 @@ impl Parser {
     /// This method expects the attribute value to be enclosed in single (''') or double ('"') quotes.
     /// It parses characters until it encounters the same type of quote that opened the attribute value.
     ///
-    /// # Panics
-    ///
-    /// This method will panic if the opening quote (' or ") is not found or if the closing quote does not match the opening one.
-    ///
     /// # Returns
     ///
-    /// A string containing the parsed value of the attribute.
-    fn parse_attr_value(&mut self) -> String {
+    /// A string containing the parsed value of the attribute, or `ErrorKind::UnterminatedAttribute`
+    /// if the opening or closing quote is missing.
+    fn parse_attr_value(&mut self) -> Result<String, ParseError> {
+        if self.eof() {
+            return Err(self.error(ErrorKind::UnexpectedEof));
+        }
+        let start = self.error(ErrorKind::UnterminatedAttribute);
         let open_quote = self.consume_char();
-        assert!(open_quote == '"' || open_quote == '\'');
+        if open_quote != '"' && open_quote != '\'' {
+            return Err(start);
+        }
         let value = self.consume_while(|c| c != open_quote);
-        assert_eq!(self.consume_char(), open_quote);
-        value
+        if self.eof() {
+            return Err(start);
+        }
+        self.consume_char();
+        Ok(if self.decode_entities { decode_entities(&value) } else { value })
     }
 
     /// Parses all attributes within an HTML element tag.
     ///
     /// This method iterates through the input string, parsing attribute name-value pairs using `parse_attr()`.
-    /// It stops when encountering the '>' character, which indicates the end of the attributes.
+    /// It stops when encountering the '>' character, or the '/' of a self-closing tag, which
+    /// indicate the end of the attributes.
     ///
     /// # Returns
     ///
     /// A `HashMap` containing the parsed attributes where keys are attribute names and values are attribute values.
-    fn parse_attributes(&mut self) -> AttrMap {
+    fn parse_attributes(&mut self) -> Result<AttrMap, ParseError> {
         let mut attrs = AttrMap::new();
         loop {
             self.consume_whitespace();
-            if self.next_char() == '>' {
+            if self.eof() {
+                return Err(self.error(ErrorKind::UnexpectedEof));
+            }
+            if matches!(self.next_char(), '>' | '/') {
                 break;
             }
-            let (name, value) = self.parse_attr();
+            let (name, value) = self.parse_attr()?;
             attrs.insert(name, value);
         }
-        attrs
+        Ok(attrs)
     }
 
     /// Parses an HTML document string and returns the root element of the parsed DOM tree.
@@ -250,17 +630,228 @@ impl Parser {
     ///
     /// # Returns
     ///
-    /// A `Node` representing the root element of the parsed DOM tree.
+    /// A `Node` representing the root element of the parsed DOM tree, or a `ParseError` with the
+    /// source position of the first problem encountered.
     /// If the document contains a single root element, it is directly returned.
-    /// Otherwise, if the document has multiple root nodes, an 'html' element is created to contain all parsed nodes.
-    pub fn parse(source: String) -> Node {
-        let mut nodes = Parser { pos: 0, input: source }.parse_nodes();
+    /// A leading `DOCTYPE`/comment alongside exactly one root element (the common
+    /// `<!DOCTYPE html><html>...</html>` shape) doesn't count as "multiple roots": the element is
+    /// returned directly and the sibling is dropped, rather than nesting the real root element
+    /// inside a synthetic wrapper. Otherwise, if the document has multiple root elements, an
+    /// 'html' element is created to contain all parsed nodes.
+    pub fn parse(source: String) -> Result<Node, ParseError> {
+        Parser::new().parse_document(source)
+    }
+
+    /// Parses an HTML document string like `parse`, but in lenient/recovery mode: a mismatched
+    /// closing tag auto-closes the currently open element instead of producing an error, so
+    /// broken markup still yields a best-effort tree.
+    pub fn parse_lenient(source: String) -> Result<Node, ParseError> {
+        Parser::new().with_recovery(true).parse_document(source)
+    }
+
+    /// Runs this (possibly configured via `with_recovery`/`with_entity_decoding`) parser over
+    /// `source` and returns the root element of the parsed DOM tree.
+    pub fn parse_document(mut self, source: String) -> Result<Node, ParseError> {
+        self.input = source;
+        let mut nodes = self.parse_nodes()?;
+
+        let element_count = nodes.iter().filter(|n| matches!(n.node_type(), NodeType::Element(_))).count();
+        if element_count <= 1 {
+            if let Some(pos) = nodes.iter().position(|n| matches!(n.node_type(), NodeType::Element(_))) {
+                return Ok(nodes.swap_remove(pos));
+            }
+        }
 
-        // If the document contains a root element, just return it. Otherwise, create one.
         if nodes.len() == 1 {
-            nodes.swap_remove(0)
+            Ok(nodes.swap_remove(0))
         } else {
-            element("html".to_string(), HashMap::new(), nodes)
+            Ok(element("html".to_string(), HashMap::new(), nodes))
+        }
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::node::NodeType;
+
+    fn tag(node: &Node) -> &str {
+        match node.node_type() {
+            NodeType::Element(data) => data.tag_name(),
+            _ => panic!("expected an element node"),
+        }
+    }
+
+    fn text_content(node: &Node) -> &str {
+        match node.node_type() {
+            NodeType::Text(s) => s.as_str(),
+            _ => panic!("expected a text node"),
+        }
+    }
+
+    #[test]
+    fn comment_with_nested_double_dash_is_not_terminated_early() {
+        let root = Parser::parse("<!-- a -- b -->".to_string()).unwrap();
+        match root.node_type() {
+            NodeType::Comment(content) => assert_eq!(content, " a -- b "),
+            _ => panic!("expected a comment node"),
+        }
+    }
+
+    #[test]
+    fn unterminated_comment_consumes_to_eof() {
+        let root = Parser::parse("<!-- never closed".to_string()).unwrap();
+        match root.node_type() {
+            NodeType::Comment(content) => assert_eq!(content, " never closed"),
+            _ => panic!("expected a comment node"),
+        }
+    }
+
+    #[test]
+    fn doctype_is_recognized_by_position_not_keyword() {
+        let root = Parser::parse("<!DOCTYPE html>".to_string()).unwrap();
+        match root.node_type() {
+            NodeType::Doctype(content) => assert_eq!(content, "DOCTYPE html"),
+            _ => panic!("expected a doctype node"),
+        }
+    }
+
+    #[test]
+    fn mismatched_closing_tag_errors_in_strict_mode() {
+        let err = match Parser::parse("<div><span>text</div>".to_string()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected a parse error"),
+        };
+        assert_eq!(
+            err.kind,
+            ErrorKind::MismatchedClosingTag { expected: "span".to_string(), found: "div".to_string() }
+        );
+    }
+
+    #[test]
+    fn parse_error_pins_exact_source_position() {
+        // "<div>\n  <span>text" never closes </span>, so parsing runs off the end of the
+        // input while still inside parse_element. That EOF is hit on line 2 (0-based column 12,
+        // matching `Parser::col`), at byte offset 18 — the full length of the source.
+        let err = match Parser::parse("<div>\n  <span>text".to_string()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected a parse error"),
+        };
+        assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 12);
+        assert_eq!(err.byte_offset, 18);
+    }
+
+    #[test]
+    fn mismatched_closing_tag_recovers_in_lenient_mode() {
+        let root = Parser::parse_lenient("<div><span>text</div>".to_string()).unwrap();
+        assert_eq!(tag(&root), "div");
+        assert_eq!(tag(&root.children()[0]), "span");
+    }
+
+    #[test]
+    fn eof_with_no_closing_tag_errors_in_strict_mode() {
+        let err = match Parser::parse("<div><span>text".to_string()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected a parse error"),
+        };
+        assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn eof_with_no_closing_tag_recovers_in_lenient_mode() {
+        let root = Parser::parse_lenient("<div><span>text".to_string()).unwrap();
+        assert_eq!(tag(&root), "div");
+        assert_eq!(tag(&root.children()[0]), "span");
+    }
+
+    #[test]
+    fn named_numeric_and_hex_entities_decode() {
+        let root = Parser::parse("<p>&amp; &#169; &#x2764;</p>".to_string()).unwrap();
+        assert_eq!(text_content(&root.children()[0]), "& \u{a9} \u{2764}");
+    }
+
+    #[test]
+    fn unknown_entity_is_left_unchanged() {
+        let root = Parser::parse("<p>&notreal;</p>".to_string()).unwrap();
+        assert_eq!(text_content(&root.children()[0]), "&notreal;");
+    }
+
+    #[test]
+    fn void_element_has_no_children_and_no_closing_tag() {
+        let root = Parser::parse("<div><br></div>".to_string()).unwrap();
+        assert_eq!(tag(&root.children()[0]), "br");
+        assert!(root.children()[0].children().is_empty());
+    }
+
+    #[test]
+    fn explicit_self_closing_tag_has_no_children() {
+        let root = Parser::parse("<div><span/></div>".to_string()).unwrap();
+        assert_eq!(tag(&root.children()[0]), "span");
+        assert!(root.children()[0].children().is_empty());
+    }
+
+    #[test]
+    fn boolean_attribute_parses_with_empty_value() {
+        let root = Parser::parse("<input disabled/>".to_string()).unwrap();
+        match root.node_type() {
+            NodeType::Element(data) => assert_eq!(data.attributes().get("disabled"), Some(&String::new())),
+            _ => panic!("expected an element node"),
         }
     }
+
+    #[test]
+    fn invalid_attribute_start_is_an_error() {
+        let err = match Parser::parse("<div \"bad\">".to_string()) {
+            Err(e) => e,
+            Ok(_) => panic!("expected a parse error"),
+        };
+        assert_eq!(err.kind, ErrorKind::InvalidAttributeName { found: '"' });
+    }
+
+    #[test]
+    fn script_content_is_not_parsed_as_markup() {
+        let root = Parser::parse("<script>if (1 < 2) { }</script>".to_string()).unwrap();
+        assert_eq!(tag(&root), "script");
+        assert_eq!(text_content(&root.children()[0]), "if (1 < 2) { }");
+    }
+
+    #[test]
+    fn raw_text_closing_tag_allows_whitespace_before_gt() {
+        let root = Parser::parse("<script>var x=1;</script >".to_string()).unwrap();
+        assert_eq!(text_content(&root.children()[0]), "var x=1;");
+    }
+
+    #[test]
+    fn script_content_is_not_entity_decoded() {
+        let root = Parser::parse("<script>a &amp; b</script>".to_string()).unwrap();
+        assert_eq!(text_content(&root.children()[0]), "a &amp; b");
+    }
+
+    #[test]
+    fn title_content_is_entity_decoded() {
+        let root = Parser::parse("<title>AT&amp;T</title>".to_string()).unwrap();
+        assert_eq!(text_content(&root.children()[0]), "AT&T");
+    }
+
+    #[test]
+    fn multiple_root_nodes_are_wrapped_in_html_element() {
+        let root = Parser::parse("<div></div><p></p>".to_string()).unwrap();
+        assert_eq!(tag(&root), "html");
+        assert_eq!(root.children().len(), 2);
+    }
+
+    #[test]
+    fn doctype_followed_by_single_root_element_is_not_wrapped() {
+        let root = Parser::parse("<!DOCTYPE html><html><body></body></html>".to_string()).unwrap();
+        assert_eq!(tag(&root), "html");
+        assert_eq!(tag(&root.children()[0]), "body");
+    }
 }
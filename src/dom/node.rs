@@ -13,6 +13,24 @@ pub struct ElementData {
 }
 
 impl ElementData {
+    /// Returns the tag name of the element (e.g. `"div"`).
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - A reference to the element's tag name.
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    /// Returns the element's attributes.
+    ///
+    /// # Returns
+    ///
+    /// * `&AttrMap` - A reference to the map of attribute names to values.
+    pub fn attributes(&self) -> &AttrMap {
+        &self.attributes
+    }
+
     /// Returns an optional reference to the `String` associated with the "id" attribute.
     ///
     /// # Returns
@@ -45,6 +63,8 @@ pub enum NodeType {
     Element(ElementData),
     /// Comment node containing comment text.
     Comment(String),
+    /// Doctype declaration (e.g. `<!DOCTYPE html>`), storing the raw text between `<!` and `>`.
+    Doctype(String),
 }
 
 /// Represents a node in the DOM tree, containing child nodes and node type information.
@@ -55,7 +75,341 @@ pub struct Node {
     pub(crate) node_type: NodeType,
 }
 
+/// HTML void elements: they never have children and are serialized without a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// CDATA tags whose text content is emitted verbatim (no entity re-encoding) when serializing.
+/// `textarea`/`title` are RCDATA rather than true CDATA — the parser decodes character references
+/// in their content, so it's re-encoded like ordinary text to round-trip correctly.
+const RAW_TEXT_TAGS: &[&str] = &["script", "style"];
+
+/// Escapes `&`, `<`, `>` (and `"` when `is_attribute` is set) as HTML entities.
+fn encode_entities(input: &str, is_attribute: bool) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if is_attribute => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Options controlling how `Node::to_html` serializes a DOM tree back to a string.
+pub struct RenderOptions {
+    /// Indent nested elements with two spaces per level and put each node on its own line.
+    /// When `false`, output is minified with no extra whitespace.
+    pub pretty: bool,
+    /// Self-close void elements as `<br/>` instead of `<br>`.
+    pub self_close_void: bool,
+    /// Re-encode `<`, `>`, `&`, `"` in text and attribute values as entities.
+    pub encode_entities: bool,
+    /// Emit comment nodes; when `false`, comments are dropped from the output.
+    pub preserve_comments: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            pretty: true,
+            self_close_void: false,
+            encode_entities: true,
+            preserve_comments: true,
+        }
+    }
+}
+
+/// A single simple selector: a tag name, `#id`, `.class`, or the universal `*`.
+enum SimpleSelector {
+    Tag(String),
+    Id(String),
+    Class(String),
+    Universal,
+}
+
+impl SimpleSelector {
+    fn matches(&self, element: &ElementData) -> bool {
+        match self {
+            SimpleSelector::Tag(tag) => element.tag_name == *tag,
+            SimpleSelector::Id(id) => element.id() == Some(id),
+            SimpleSelector::Class(class) => element.classes().contains(class.as_str()),
+            SimpleSelector::Universal => true,
+        }
+    }
+}
+
+/// A sequence of simple selectors that must all match the same element, e.g. `div.container#main`.
+struct CompoundSelector(Vec<SimpleSelector>);
+
+impl CompoundSelector {
+    /// Parses a single compound selector such as `div`, `#main`, `.container`, `div.container#main`, or `*`.
+    fn parse(selector: &str) -> CompoundSelector {
+        let mut simples = Vec::new();
+
+        let tag_len = selector.find(['#', '.']).unwrap_or(selector.len());
+        let (tag_part, mut rest) = selector.split_at(tag_len);
+        if tag_part == "*" {
+            simples.push(SimpleSelector::Universal);
+        } else if !tag_part.is_empty() {
+            simples.push(SimpleSelector::Tag(tag_part.to_string()));
+        }
+
+        while !rest.is_empty() {
+            let marker = rest.as_bytes()[0] as char;
+            let body = &rest[1..];
+            let name_len = body.find(['#', '.']).unwrap_or(body.len());
+            let (name, remainder) = body.split_at(name_len);
+            if !name.is_empty() {
+                simples.push(match marker {
+                    '#' => SimpleSelector::Id(name.to_string()),
+                    '.' => SimpleSelector::Class(name.to_string()),
+                    _ => unreachable!("split only occurs at '#' or '.'"),
+                });
+            }
+            rest = remainder;
+        }
+
+        CompoundSelector(simples)
+    }
+
+    fn matches(&self, element: &ElementData) -> bool {
+        self.0.iter().all(|simple| simple.matches(element))
+    }
+}
+
+/// A CSS selector: a list of compound selectors separated by descendant combinators (whitespace).
+struct Selector(Vec<CompoundSelector>);
+
+impl Selector {
+    fn parse(selector: &str) -> Selector {
+        Selector(selector.split_whitespace().map(CompoundSelector::parse).collect())
+    }
+
+    /// Checks whether `element`, reached via `ancestors` (root-to-parent, nearest last), matches
+    /// this selector: the last compound must match `element` itself, and each earlier compound
+    /// must match some ancestor along the path, in order.
+    fn matches(&self, ancestors: &[&ElementData], element: &ElementData) -> bool {
+        let Some((last, rest)) = self.0.split_last() else {
+            return false;
+        };
+        if !last.matches(element) {
+            return false;
+        }
+
+        let mut remaining = rest;
+        for ancestor in ancestors.iter().rev() {
+            if remaining.is_empty() {
+                break;
+            }
+            if remaining.last().unwrap().matches(ancestor) {
+                remaining = &remaining[..remaining.len() - 1];
+            }
+        }
+        remaining.is_empty()
+    }
+}
+
 impl Node {
+    /// Returns this node's type (text, element, comment, or doctype), for inspecting its content
+    /// from outside the crate.
+    ///
+    /// # Returns
+    ///
+    /// * `&NodeType` - A reference to the node's type.
+    pub fn node_type(&self) -> &NodeType {
+        &self.node_type
+    }
+
+    /// Returns this node's children.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Node]` - A slice of the node's child nodes, empty if it has none.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// Returns the first descendant (depth-first, self included) matching the given CSS
+    /// selector, reusing `ElementData::id()`/`ElementData::classes()` for matching.
+    ///
+    /// Supports tag names, `#id`, `.class`, `*`, and whitespace-separated descendant combinators
+    /// (e.g. `div.container #main span`).
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - A CSS selector string.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Node>` - The first matching descendant, or `None` if nothing matches.
+    pub fn query_selector(&self, selector: &str) -> Option<&Node> {
+        let selector = Selector::parse(selector);
+        let mut ancestors = Vec::new();
+        self.find_first(&selector, &mut ancestors)
+    }
+
+    /// Returns every descendant (depth-first, self included) matching the given CSS selector.
+    /// See `query_selector` for the supported selector syntax.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - A CSS selector string.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Node>` - Every matching descendant, in depth-first order.
+    pub fn query_selector_all(&self, selector: &str) -> Vec<&Node> {
+        let selector = Selector::parse(selector);
+        let mut ancestors = Vec::new();
+        let mut results = Vec::new();
+        self.find_all(&selector, &mut ancestors, &mut results);
+        results
+    }
+
+    fn find_first<'a>(&'a self, selector: &Selector, ancestors: &mut Vec<&'a ElementData>) -> Option<&'a Node> {
+        if let NodeType::Element(data) = &self.node_type {
+            if selector.matches(ancestors, data) {
+                return Some(self);
+            }
+            ancestors.push(data);
+            let found = self.children.iter().find_map(|child| child.find_first(selector, ancestors));
+            ancestors.pop();
+            return found;
+        }
+        None
+    }
+
+    fn find_all<'a>(&'a self, selector: &Selector, ancestors: &mut Vec<&'a ElementData>, results: &mut Vec<&'a Node>) {
+        if let NodeType::Element(data) = &self.node_type {
+            if selector.matches(ancestors, data) {
+                results.push(self);
+            }
+            ancestors.push(data);
+            for child in &self.children {
+                child.find_all(selector, ancestors, results);
+            }
+            ancestors.pop();
+        }
+    }
+
+    /// Serializes this node (and its descendants) back to an HTML string according to `opts`.
+    ///
+    /// Attributes are emitted in sorted order with quoted values so the output is deterministic.
+    /// CDATA elements (`<script>`, `<style>`) emit their text child verbatim regardless of
+    /// `opts.encode_entities`, since re-encoding would corrupt embedded JS/CSS.
+    ///
+    /// # Arguments
+    ///
+    /// * `opts` - A `&RenderOptions` controlling pretty-printing, void-element self-closing,
+    ///   entity encoding, and comment preservation.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The serialized HTML.
+    pub fn to_html(&self, opts: &RenderOptions) -> String {
+        let mut out = String::new();
+        self.write_html(&mut out, 0, opts);
+        out
+    }
+
+    fn write_html(&self, out: &mut String, indent: usize, opts: &RenderOptions) {
+        match &self.node_type {
+            NodeType::Element(data) => {
+                if opts.pretty {
+                    out.push_str(&"  ".repeat(indent));
+                }
+                out.push('<');
+                out.push_str(&data.tag_name);
+
+                let mut names: Vec<&String> = data.attributes.keys().collect();
+                names.sort();
+                for name in names {
+                    let value = &data.attributes[name];
+                    let value = if opts.encode_entities { encode_entities(value, true) } else { value.clone() };
+                    out.push(' ');
+                    out.push_str(name);
+                    out.push_str("=\"");
+                    out.push_str(&value);
+                    out.push('"');
+                }
+
+                if VOID_ELEMENTS.contains(&data.tag_name.as_str()) {
+                    out.push_str(if opts.self_close_void { "/>" } else { ">" });
+                    if opts.pretty {
+                        out.push('\n');
+                    }
+                    return;
+                }
+                out.push('>');
+
+                if RAW_TEXT_TAGS.contains(&data.tag_name.as_str()) {
+                    for child in &self.children {
+                        if let NodeType::Text(text) = &child.node_type {
+                            out.push_str(text);
+                        }
+                    }
+                } else {
+                    if opts.pretty && !self.children.is_empty() {
+                        out.push('\n');
+                    }
+                    for child in &self.children {
+                        child.write_html(out, indent + 1, opts);
+                    }
+                    if opts.pretty && !self.children.is_empty() {
+                        out.push_str(&"  ".repeat(indent));
+                    }
+                }
+
+                out.push_str("</");
+                out.push_str(&data.tag_name);
+                out.push('>');
+                if opts.pretty {
+                    out.push('\n');
+                }
+            }
+            NodeType::Text(text) => {
+                if opts.pretty {
+                    out.push_str(&"  ".repeat(indent));
+                }
+                out.push_str(&if opts.encode_entities { encode_entities(text, false) } else { text.clone() });
+                if opts.pretty {
+                    out.push('\n');
+                }
+            }
+            NodeType::Comment(comment) => {
+                if !opts.preserve_comments {
+                    return;
+                }
+                if opts.pretty {
+                    out.push_str(&"  ".repeat(indent));
+                }
+                out.push_str("<!--");
+                out.push_str(comment);
+                out.push_str("-->");
+                if opts.pretty {
+                    out.push('\n');
+                }
+            }
+            NodeType::Doctype(content) => {
+                if opts.pretty {
+                    out.push_str(&"  ".repeat(indent));
+                }
+                out.push_str("<!");
+                out.push_str(content);
+                out.push('>');
+                if opts.pretty {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
     pub fn pretty_print(&self, indent: usize) {
         match &self.node_type {
             NodeType::Element(element_data) => {
@@ -76,6 +430,9 @@ impl Node {
             NodeType::Comment(comment) => {
                 println!("{:indent$}<!-- {} -->", "", comment, indent = indent * 2);
             }
+            NodeType::Doctype(content) => {
+                println!("{:indent$}<!{}>", "", content, indent = indent * 2);
+            }
         }
     }
 }
@@ -112,4 +469,133 @@ pub fn element(name: String, attrs: AttrMap, children: Vec<Node>) -> Node {
             attributes: attrs,
         }),
     }
+}
+
+/// Creates a new comment node with the given comment text.
+///
+/// # Arguments
+///
+/// * `data` - A `String` containing the comment text for the node.
+///
+/// # Returns
+///
+/// A `Node` representing a comment node with the provided text and no children.
+pub fn comment(data: String) -> Node {
+    Node { children: vec![], node_type: NodeType::Comment(data) }
+}
+
+/// Creates a new doctype node with the given declaration text.
+///
+/// # Arguments
+///
+/// * `data` - A `String` containing the raw text between `<!` and `>`.
+///
+/// # Returns
+///
+/// A `Node` representing a doctype node with the provided text and no children.
+pub fn doctype(data: String) -> Node {
+    Node { children: vec![], node_type: NodeType::Doctype(data) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tree() -> Node {
+        let mut inner_attrs = AttrMap::new();
+        inner_attrs.insert("id".to_string(), "main".to_string());
+        inner_attrs.insert("class".to_string(), "container highlighted".to_string());
+
+        element(
+            "div".to_string(),
+            AttrMap::new(),
+            vec![
+                element("div".to_string(), inner_attrs, vec![text("Hello & welcome".to_string())]),
+                element("p".to_string(), AttrMap::new(), vec![comment("a note".to_string())]),
+            ],
+        )
+    }
+
+    #[test]
+    fn query_selector_matches_by_tag() {
+        // Exercises only the public accessors (`node_type()`/`ElementData::tag_name()`), the way
+        // an external consumer of this crate would have to.
+        let root = sample_tree();
+        let found = root.query_selector("p").unwrap();
+        assert!(matches!(found.node_type(), NodeType::Element(data) if data.tag_name() == "p"));
+    }
+
+    #[test]
+    fn query_selector_matches_by_id_and_class() {
+        let root = sample_tree();
+        assert!(root.query_selector("#main").is_some());
+        assert!(root.query_selector(".highlighted").is_some());
+        assert!(root.query_selector(".missing").is_none());
+    }
+
+    #[test]
+    fn query_selector_matches_descendant_combinator() {
+        let root = sample_tree();
+        assert!(root.query_selector("div div").is_some());
+        assert!(root.query_selector("p div").is_none());
+    }
+
+    #[test]
+    fn query_selector_all_returns_every_match() {
+        let root = sample_tree();
+        assert_eq!(root.query_selector_all("div").len(), 2);
+    }
+
+    #[test]
+    fn to_html_encodes_entities_by_default() {
+        let root = sample_tree();
+        let html = root.to_html(&RenderOptions::default());
+        assert!(html.contains("Hello &amp; welcome"));
+    }
+
+    #[test]
+    fn to_html_can_drop_comments() {
+        let root = sample_tree();
+        let opts = RenderOptions { preserve_comments: false, ..RenderOptions::default() };
+        let html = root.to_html(&opts);
+        assert!(!html.contains("a note"));
+    }
+
+    #[test]
+    fn to_html_self_closes_void_elements_when_requested() {
+        let root = element("br".to_string(), AttrMap::new(), vec![]);
+        let opts = RenderOptions { pretty: false, self_close_void: true, ..RenderOptions::default() };
+        assert_eq!(root.to_html(&opts), "<br/>");
+    }
+
+    #[test]
+    fn tree_is_inspectable_through_public_accessors_only() {
+        // `node_type`/`children` on `Node` and `tag_name`/`attributes` on `ElementData` are the
+        // only way a consumer outside this crate can inspect a parsed tree (its fields are
+        // `pub(crate)`). This walks `sample_tree()` using nothing else, the way a scraper would.
+        let root = sample_tree();
+        let mut tags = Vec::new();
+        let mut stack = vec![&root];
+        while let Some(node) = stack.pop() {
+            if let NodeType::Element(data) = node.node_type() {
+                tags.push(data.tag_name().to_string());
+            }
+            stack.extend(node.children().iter());
+        }
+        tags.sort();
+        assert_eq!(tags, vec!["div", "div", "p"]);
+
+        let inner = root.query_selector("#main").unwrap();
+        match inner.node_type() {
+            NodeType::Element(data) => assert_eq!(data.attributes().get("id").map(String::as_str), Some("main")),
+            _ => panic!("expected an element node"),
+        }
+    }
+
+    #[test]
+    fn to_html_emits_script_content_verbatim() {
+        let root = element("script".to_string(), AttrMap::new(), vec![text("a & b < c".to_string())]);
+        let opts = RenderOptions { pretty: false, ..RenderOptions::default() };
+        assert_eq!(root.to_html(&opts), "<script>a & b < c</script>");
+    }
 }
\ No newline at end of file